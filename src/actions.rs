@@ -1,27 +1,57 @@
-use diesel::{prelude::*, delete};
+use std::collections::HashMap;
+
+use diesel::{dsl::count_star, prelude::*, delete};
 use uuid::Uuid;
 
-use crate::model::task;
+use crate::model::{board, task, user};
 
 type DbError = Box<dyn std::error::Error + Send + Sync>;
 
-/// Query to get all tasks
-pub fn find_all_tasks(conn: &mut SqliteConnection) -> Result<Vec<task::Task>, DbError> {
+/// Query to get tasks belonging to the given user, applying the requested
+/// filters/pagination, alongside the total count ignoring `limit`/`offset`.
+pub fn find_all_tasks(
+    conn: &mut SqliteConnection,
+    owner: Uuid,
+    params: &task::TaskListParams,
+) -> Result<(Vec<task::Task>, i64), DbError> {
     use crate::schema::tasks::dsl::*;
 
-    let get_tasks = tasks.load::<task::Task>(conn)?;
-    Ok(get_tasks)
+    let mut count_query = tasks.filter(user_id.eq(owner.to_string())).into_boxed();
+    let mut query = tasks.filter(user_id.eq(owner.to_string())).into_boxed();
+
+    if let Some(is_done) = params.done {
+        count_query = count_query.filter(done.eq(is_done));
+        query = query.filter(done.eq(is_done));
+    }
+    if let Some(ref needle) = params.name {
+        count_query = count_query.filter(name.like(format!("%{needle}%")));
+        query = query.filter(name.like(format!("%{needle}%")));
+    }
+
+    let total = count_query.count().get_result::<i64>(conn)?;
+
+    if let Some(lim) = params.limit {
+        query = query.limit(lim);
+    }
+    if let Some(off) = params.offset {
+        query = query.offset(off);
+    }
+
+    let get_tasks = query.load::<task::Task>(conn)?;
+    Ok((get_tasks, total))
 }
 
-/// Run query using Diesel to find task by uid and return it.
+/// Run query using Diesel to find task by uid, scoped to its owner, and return it.
 pub fn find_task_by_uid(
     conn: &mut SqliteConnection,
     uid: Uuid,
+    owner: Uuid,
 ) -> Result<Option<task::Task>, DbError> {
     use crate::schema::tasks::dsl::*;
 
     let task = tasks
         .filter(id.eq(uid.to_string()))
+        .filter(user_id.eq(owner.to_string()))
         .first::<task::Task>(conn)
         .optional()?;
 
@@ -33,16 +63,23 @@ pub fn insert_new_task(
     conn: &mut SqliteConnection,
     nm: &str,
     dn: &bool,
+    brd_id: Option<&str>,
+    owner: Uuid,
 ) -> Result<task::Task, DbError> {
     // It is common when using Diesel with Actix Web to import schema-related
     // modules inside a function's scope (rather than the normal module's scope)
     // to prevent import collisions and namespace pollution.
     use crate::schema::tasks::dsl::*;
 
+    let status = if *dn { task::TaskStatus::Done } else { task::TaskStatus::Queued };
+
     let new_task = task::Task {
         id: Uuid::new_v4().to_string(),
         name: nm.to_owned(),
         done: *dn,
+        board_id: brd_id.map(str::to_owned),
+        status: status.as_str().to_owned(),
+        user_id: Some(owner.to_string()),
     };
 
     diesel::insert_into(tasks).values(&new_task).execute(conn)?;
@@ -50,7 +87,184 @@ pub fn insert_new_task(
     Ok(new_task)
 }
 
-pub fn destroy_task(conn: &mut SqliteConnection, uid: Uuid) -> Result<usize, diesel::result::Error> {
+pub fn destroy_task(conn: &mut SqliteConnection, uid: Uuid, owner: Uuid) -> Result<usize, DbError> {
   use crate::schema::tasks::dsl::*;
-  Ok(delete(tasks.filter(id.eq(uid.to_string()))).execute(conn)?)
+  Ok(delete(tasks.filter(id.eq(uid.to_string())).filter(user_id.eq(owner.to_string()))).execute(conn)?)
+}
+
+/// Applies a partial update to the task with the given uid, scoped to its
+/// owner, and returns the updated row, or `None` if no such task exists.
+///
+/// `done` and `status` are kept in sync whenever only one of the two is
+/// set: if `status` is omitted, it's derived from `done`; if `done` is
+/// omitted, it's derived from `status` (`done = status == "done"`). Setting
+/// both explicitly (e.g. `status: "in_progress", done: false`) always wins.
+pub fn update_task(
+    conn: &mut SqliteConnection,
+    uid: Uuid,
+    owner: Uuid,
+    changes: &task::UpdateTask,
+) -> Result<Option<task::Task>, DbError> {
+    use crate::schema::tasks::dsl::*;
+
+    let mut changes = changes.clone();
+    match (&changes.status, changes.done) {
+        (None, Some(is_done)) => {
+            let derived = if is_done { task::TaskStatus::Done } else { task::TaskStatus::Queued };
+            changes.status = Some(derived.as_str().to_owned());
+        }
+        (Some(new_status), None) => {
+            changes.done = Some(new_status == task::TaskStatus::Done.as_str());
+        }
+        _ => {}
+    }
+
+    diesel::update(tasks.filter(id.eq(uid.to_string())).filter(user_id.eq(owner.to_string())))
+        .set(&changes)
+        .execute(conn)?;
+
+    let updated = tasks
+        .filter(id.eq(uid.to_string()))
+        .filter(user_id.eq(owner.to_string()))
+        .first::<task::Task>(conn)
+        .optional()?;
+
+    Ok(updated)
+}
+
+/// Run query using Diesel to insert a new user and return the result.
+pub fn create_user(
+    conn: &mut SqliteConnection,
+    uname: &str,
+    pw_hash: &str,
+) -> Result<user::User, DbError> {
+    use crate::schema::users::dsl::*;
+
+    let new_user = user::User {
+        id: Uuid::new_v4().to_string(),
+        username: uname.to_owned(),
+        password_hash: pw_hash.to_owned(),
+    };
+
+    diesel::insert_into(users).values(&new_user).execute(conn)?;
+
+    Ok(new_user)
+}
+
+/// Run query using Diesel to find a user by username.
+pub fn find_user_by_username(
+    conn: &mut SqliteConnection,
+    uname: &str,
+) -> Result<Option<user::User>, DbError> {
+    use crate::schema::users::dsl::*;
+
+    let found = users
+        .filter(username.eq(uname))
+        .first::<user::User>(conn)
+        .optional()?;
+
+    Ok(found)
+}
+
+/// Run query using Diesel to insert a new board and return the result.
+pub fn create_board(conn: &mut SqliteConnection, nm: &str) -> Result<board::Board, DbError> {
+    use crate::schema::boards::dsl::*;
+
+    let new_board = board::Board {
+        id: Uuid::new_v4().to_string(),
+        name: nm.to_owned(),
+    };
+
+    diesel::insert_into(boards).values(&new_board).execute(conn)?;
+
+    Ok(new_board)
+}
+
+/// Issues a new opaque session token for the given user, to be handed back
+/// to the client by `api::auth::login`.
+pub fn create_session(conn: &mut SqliteConnection, owner: Uuid) -> Result<user::Session, DbError> {
+    use crate::schema::sessions::dsl::*;
+
+    let new_session = user::Session {
+        token: Uuid::new_v4().to_string(),
+        user_id: owner.to_string(),
+    };
+
+    diesel::insert_into(sessions).values(&new_session).execute(conn)?;
+
+    Ok(new_session)
+}
+
+/// Looks up the user id a session token was issued to, if the token exists.
+pub fn find_session_user(conn: &mut SqliteConnection, tok: &str) -> Result<Option<String>, DbError> {
+    use crate::schema::sessions::dsl::*;
+
+    let found = sessions
+        .filter(token.eq(tok))
+        .select(user_id)
+        .first::<String>(conn)
+        .optional()?;
+
+    Ok(found)
+}
+
+/// Query to get all boards
+pub fn find_all_boards(conn: &mut SqliteConnection) -> Result<Vec<board::Board>, DbError> {
+    use crate::schema::boards::dsl::*;
+
+    let get_boards = boards.load::<board::Board>(conn)?;
+    Ok(get_boards)
+}
+
+pub fn delete_board(conn: &mut SqliteConnection, uid: Uuid) -> Result<usize, diesel::result::Error> {
+    use crate::schema::boards::dsl::*;
+    Ok(delete(boards.filter(id.eq(uid.to_string()))).execute(conn)?)
+}
+
+/// Query to get the tasks belonging to a board, scoped to their owner so a
+/// caller only ever sees their own tasks on the board.
+pub fn find_tasks_by_board(
+    conn: &mut SqliteConnection,
+    board_uid: Uuid,
+    owner: Uuid,
+) -> Result<Vec<task::Task>, DbError> {
+    use crate::schema::tasks::dsl::*;
+
+    let get_tasks = tasks
+        .filter(board_id.eq(board_uid.to_string()))
+        .filter(user_id.eq(owner.to_string()))
+        .load::<task::Task>(conn)?;
+
+    Ok(get_tasks)
+}
+
+/// Counts the tasks on a board grouped by `status`, e.g.
+/// `{ "queued": 3, "in_progress": 1, "done": 5 }`.
+///
+/// All three `TaskStatus` variants are always present in the result, even
+/// when a board has zero tasks in that status.
+pub fn board_task_summary(
+    conn: &mut SqliteConnection,
+    board_uid: Uuid,
+) -> Result<HashMap<String, i64>, DbError> {
+    use crate::schema::tasks::dsl::*;
+
+    let counts = tasks
+        .filter(board_id.eq(board_uid.to_string()))
+        .group_by(status)
+        .select((status, count_star()))
+        .load::<(String, i64)>(conn)?;
+
+    let mut summary: HashMap<String, i64> = [
+        task::TaskStatus::Queued,
+        task::TaskStatus::InProgress,
+        task::TaskStatus::Done,
+    ]
+    .into_iter()
+    .map(|status| (status.as_str().to_owned(), 0))
+    .collect();
+
+    summary.extend(counts);
+
+    Ok(summary)
 }
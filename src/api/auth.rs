@@ -0,0 +1,74 @@
+use actix_web::{error, post, web, HttpResponse, Responder, Result};
+use uuid::Uuid;
+
+use crate::actions;
+use crate::initdb::{self, DbPool};
+use crate::model::user;
+
+/// Bcrypt cost factor, configurable via `HASH_COST` since hashing gets
+/// slower (deliberately) as the cost increases.
+fn hash_cost() -> u32 {
+    std::env::var("HASH_COST")
+        .ok()
+        .and_then(|cost| cost.parse().ok())
+        .unwrap_or(bcrypt::DEFAULT_COST)
+}
+
+/// Registers a new user, hashing the password with bcrypt.
+#[post("/register")]
+async fn register(
+    pool: web::Data<DbPool>,
+    form: web::Json<user::NewUser>,
+) -> Result<impl Responder> {
+    let cost = hash_cost();
+    let password = form.password.clone();
+
+    // bcrypt hashing is CPU-bound, so it runs on a blocking thread like the
+    // Diesel calls elsewhere in this crate.
+    let hash = web::block(move || bcrypt::hash(password, cost))
+        .await?
+        .map_err(error::ErrorInternalServerError)?;
+
+    let username = form.username.clone();
+    let created = initdb::run(&pool, move |conn| actions::create_user(conn, &username, &hash))
+        .await?;
+
+    Ok(HttpResponse::Created().json(user::UserResponse::from(created)))
+}
+
+/// Verifies credentials and returns a new session token along with the
+/// matching user.
+#[post("/login")]
+async fn login(
+    pool: web::Data<DbPool>,
+    form: web::Json<user::Credentials>,
+) -> Result<impl Responder> {
+    let username = form.username.clone();
+    let found = initdb::run(&pool, move |conn| actions::find_user_by_username(conn, &username))
+        .await?;
+
+    let found_user = match found {
+        Some(found_user) => found_user,
+        None => return Ok(HttpResponse::Unauthorized().body("invalid username or password")),
+    };
+
+    let password = form.password.clone();
+    let hash = found_user.password_hash.clone();
+    let valid = web::block(move || bcrypt::verify(password, &hash))
+        .await?
+        .map_err(error::ErrorInternalServerError)?;
+
+    if !valid {
+        return Ok(HttpResponse::Unauthorized().body("invalid username or password"));
+    }
+
+    // `found_user.id` was generated with `Uuid::new_v4()` in `create_user`,
+    // so parsing it back can't fail outside of a corrupted database.
+    let user_id = Uuid::parse_str(&found_user.id).map_err(error::ErrorInternalServerError)?;
+    let session = initdb::run(&pool, move |conn| actions::create_session(conn, user_id)).await?;
+
+    Ok(HttpResponse::Ok().json(user::LoginResponse {
+        token: session.token,
+        user: user::UserResponse::from(found_user),
+    }))
+}
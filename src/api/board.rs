@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+
+use actix_web::{delete, get, post, web};
+use uuid::Uuid;
+
+use crate::actions;
+use crate::api_response::ApiResponse;
+use crate::auth::AuthenticatedUser;
+use crate::errors::DomainError;
+use crate::initdb::{self, DbPool};
+use crate::model::{board, task};
+
+/// Creates a new board.
+#[post("/board")]
+async fn create_board(
+    pool: web::Data<DbPool>,
+    form: web::Json<board::NewBoard>,
+) -> Result<ApiResponse<board::Board>, DomainError> {
+    let board = initdb::run(&pool, move |conn| actions::create_board(conn, &form.name)).await?;
+
+    Ok(ApiResponse::created(board))
+}
+
+/// Get all boards
+#[get("/boards")]
+async fn get_all_boards(
+    pool: web::Data<DbPool>,
+) -> Result<ApiResponse<Vec<board::Board>>, DomainError> {
+    let boards = initdb::run(&pool, actions::find_all_boards).await?;
+
+    Ok(ApiResponse::ok(boards))
+}
+
+/// Lists the authenticated user's tasks that belong to a board.
+#[get("/board/{board_id}/tasks")]
+async fn get_board_tasks(
+    pool: web::Data<DbPool>,
+    board_uid: web::Path<Uuid>,
+    user: AuthenticatedUser,
+) -> Result<ApiResponse<Vec<task::Task>>, DomainError> {
+    let board_uid = board_uid.into_inner();
+    let tasks = initdb::run(&pool, move |conn| {
+        actions::find_tasks_by_board(conn, board_uid, user.0)
+    })
+    .await?;
+
+    Ok(ApiResponse::ok(tasks))
+}
+
+/// Counts a board's tasks grouped by status.
+#[get("/board/{board_id}/summary")]
+async fn get_board_summary(
+    pool: web::Data<DbPool>,
+    board_uid: web::Path<Uuid>,
+) -> Result<ApiResponse<HashMap<String, i64>>, DomainError> {
+    let board_uid = board_uid.into_inner();
+    let summary = initdb::run(&pool, move |conn| actions::board_task_summary(conn, board_uid)).await?;
+
+    Ok(ApiResponse::ok(summary))
+}
+
+#[delete("/board/{board_id}")]
+async fn delete_board(
+    pool: web::Data<DbPool>,
+    board_uid: web::Path<Uuid>,
+) -> Result<ApiResponse<()>, DomainError> {
+    let uid = board_uid.into_inner();
+    let rows_deleted = initdb::run(&pool, move |conn| Ok(actions::delete_board(conn, uid)?)).await?;
+
+    if rows_deleted == 0 {
+        return Err(DomainError::NotFound(format!("No board found with UID: {uid}")));
+    }
+
+    Ok(ApiResponse::ok(()))
+}
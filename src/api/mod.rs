@@ -0,0 +1,3 @@
+pub mod auth;
+pub mod board;
+pub mod tasks;
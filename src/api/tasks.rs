@@ -1,91 +1,100 @@
-use actix_web::web::Json;
-use actix_web::{
-    delete, error, get, post, web, HttpResponse, Responder, Result,
-};
-use diesel::{r2d2, SqliteConnection};
-use serde::Serialize;
+use actix_web::{delete, get, patch, post, web};
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::actions;
+use crate::api_response::{ApiResponse, Page};
+use crate::auth::AuthenticatedUser;
+use crate::errors::DomainError;
+use crate::initdb::{self, DbPool};
 use crate::model::task;
 
-type DbPool = r2d2::Pool<r2d2::ConnectionManager<SqliteConnection>>;
-
-#[derive(Serialize)]
-struct Response {
-    message: String,
-}
-
-/// Get all tasks
+/// Get tasks belonging to the authenticated user, paginated and optionally
+/// filtered by `done` and/or a `name` substring.
 #[get("/tasks")]
-async fn get_all_tasks(pool: web::Data<DbPool>) -> Result<impl Responder> {
-    let tasks = web::block(move || {
-        let mut conn = pool.get()?;
-        actions::find_all_tasks(&mut conn)
+async fn get_all_tasks(
+    pool: web::Data<DbPool>,
+    user: AuthenticatedUser,
+    params: web::Query<task::TaskListParams>,
+) -> Result<ApiResponse<Page<task::Task>>, DomainError> {
+    let (tasks, total) = initdb::run(&pool, move |conn| {
+        actions::find_all_tasks(conn, user.0, &params)
     })
-    .await?
-    .map_err(error::ErrorInternalServerError)?;
+    .await?;
 
-    Ok(HttpResponse::Ok().json(tasks))
+    Ok(ApiResponse::ok(Page { items: tasks, total }))
 }
 
 #[delete("/tasks/{task_id}")]
-async fn delete_task(pool: web::Data<DbPool>, task_uid: web::Path<Uuid>,) -> Result<Json<Response>> {
-  let uid: Uuid = task_uid.clone();
-  let conn_result = pool.get();
+async fn delete_task(
+    pool: web::Data<DbPool>,
+    task_uid: web::Path<Uuid>,
+    user: AuthenticatedUser,
+) -> Result<ApiResponse<()>, DomainError> {
+    let uid = task_uid.into_inner();
+    let rows_deleted = initdb::run(&pool, move |conn| actions::destroy_task(conn, uid, user.0)).await?;
 
-  match conn_result {
-    Ok(mut conn) => {
-      match actions::destroy_task(&mut conn, uid) {
-        Ok(_rows_deleted) => {
-          Ok(web::Json(Response { message: "deleted".to_string()}))
-        }
-        Err(err) => {
-          Ok(web::Json(Response { message: err.to_string() }))
-        }
-      }
-    }
-    Err(err) => {
-      Ok(web::Json(Response { message: err.to_string() }))
+    if rows_deleted == 0 {
+        return Err(DomainError::NotFound(format!("No task found with UID: {uid}")));
     }
-  }
+
+    Ok(ApiResponse::ok(()))
 }
 
-/// Finds task by UID.
+/// Finds task by UID, scoped to the authenticated user.
 #[get("/task/{task_id}")]
 async fn get_task(
     pool: web::Data<DbPool>,
     task_uid: web::Path<Uuid>,
-) -> Result<impl Responder> {
+    user: AuthenticatedUser,
+) -> Result<ApiResponse<task::Task>, DomainError> {
     let task_uid = task_uid.into_inner();
-    let task = web::block(move || {
-        // note that obtaining a connection from the pool is also potentially blocking
-        let mut conn = pool.get()?;
-        actions::find_task_by_uid(&mut conn, task_uid)
+    let task = initdb::run(&pool, move |conn| {
+        actions::find_task_by_uid(conn, task_uid, user.0)
     })
-    .await?
-    // map diesel query errors to a 500 error response
-    .map_err(error::ErrorInternalServerError)?;
+    .await?;
 
-    Ok(match task {
-        Some(task) => HttpResponse::Ok().json(task),
-        None => HttpResponse::NotFound().body(format!("No task found with UID: {task_uid}")),
+    match task {
+        Some(task) => Ok(ApiResponse::ok(task)),
+        None => Err(DomainError::NotFound(format!("No task found with UID: {task_uid}"))),
+    }
+}
+
+/// Applies a partial update to a task, e.g. renaming it or flipping `done`.
+#[patch("/task/{task_id}")]
+async fn update_task(
+    pool: web::Data<DbPool>,
+    task_uid: web::Path<Uuid>,
+    user: AuthenticatedUser,
+    form: web::Json<task::UpdateTask>,
+) -> Result<ApiResponse<task::Task>, DomainError> {
+    form.validate()?;
+
+    let task_uid = task_uid.into_inner();
+    let task = initdb::run(&pool, move |conn| {
+        actions::update_task(conn, task_uid, user.0, &form)
     })
+    .await?;
+
+    match task {
+        Some(task) => Ok(ApiResponse::ok(task)),
+        None => Err(DomainError::NotFound(format!("No task found with UID: {task_uid}"))),
+    }
 }
 
-/// Creates new task.
+/// Creates new task, owned by the authenticated user.
 #[post("/task")]
 async fn add_task(
     pool: web::Data<DbPool>,
+    user: AuthenticatedUser,
     form: web::Json<task::NewTask>,
-) -> Result<impl Responder> {
-    let task = web::block(move || {
-        let mut conn = pool.get()?;
+) -> Result<ApiResponse<task::Task>, DomainError> {
+    form.validate()?;
 
-        actions::insert_new_task(&mut conn, &form.name, &form.done)
+    let task = initdb::run(&pool, move |conn| {
+        actions::insert_new_task(conn, &form.name, &form.done, form.board_id.as_deref(), user.0)
     })
-    .await?
-    .map_err(error::ErrorInternalServerError)?;
+    .await?;
 
-    Ok(HttpResponse::Created().json(task))
+    Ok(ApiResponse::created(task))
 }
@@ -0,0 +1,73 @@
+use actix_web::{body::BoxBody, http::StatusCode, HttpRequest, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+use validator::ValidationErrors;
+
+/// JSON envelope shared by the task and board handlers' responses, success
+/// or `DomainError` failure, so clients see the same shape:
+/// `{ "success": bool, "data"/"error" }`. `/register` and `/login`, and
+/// extractor failures (an unparsable path segment, a missing or invalid
+/// bearer token), respond without it.
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T: Serialize> {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<ValidationErrors>,
+    #[serde(skip)]
+    status: StatusCode,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    /// A `200 OK` success envelope.
+    pub fn ok(data: T) -> Self {
+        Self { success: true, data: Some(data), error: None, errors: None, status: StatusCode::OK }
+    }
+
+    /// A `201 Created` success envelope.
+    pub fn created(data: T) -> Self {
+        Self { success: true, data: Some(data), error: None, errors: None, status: StatusCode::CREATED }
+    }
+}
+
+impl ApiResponse<()> {
+    /// An error envelope; used by `DomainError::error_response`.
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            data: None,
+            error: Some(message.into()),
+            errors: None,
+            status: StatusCode::OK,
+        }
+    }
+
+    /// A `400 Bad Request` envelope carrying field-level validation errors.
+    pub fn validation_error(errors: ValidationErrors) -> Self {
+        Self {
+            success: false,
+            data: None,
+            error: Some("validation failed".to_string()),
+            errors: Some(errors),
+            status: StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// A page of results alongside the total number of rows matching the query,
+/// regardless of `limit`/`offset`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Page<T: Serialize> {
+    pub items: Vec<T>,
+    pub total: i64,
+}
+
+impl<T: Serialize> Responder for ApiResponse<T> {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse {
+        HttpResponse::build(self.status).json(&self)
+    }
+}
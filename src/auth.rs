@@ -0,0 +1,51 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::{
+    dev::Payload,
+    error::{ErrorInternalServerError, ErrorUnauthorized},
+    web, Error, FromRequest, HttpRequest,
+};
+use uuid::Uuid;
+
+use crate::actions;
+use crate::initdb::{self, DbPool};
+
+/// The id of the authenticated user making the request.
+///
+/// Clients authenticate by calling `POST /login`, which returns an opaque
+/// session token, and then send that token back on the
+/// `Authorization: Bearer <token>` header on subsequent requests. The token
+/// is looked up against the `sessions` table on every request; it is never
+/// trusted on its own.
+pub struct AuthenticatedUser(pub Uuid);
+
+impl FromRequest for AuthenticatedUser {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let token = req
+            .headers()
+            .get("Authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::to_owned);
+
+        let pool = req.app_data::<web::Data<DbPool>>().cloned();
+
+        Box::pin(async move {
+            let token = token.ok_or_else(|| ErrorUnauthorized("missing bearer token"))?;
+            let pool = pool.ok_or_else(|| ErrorInternalServerError("db pool not configured"))?;
+
+            let user_id = initdb::run(&pool, move |conn| actions::find_session_user(conn, &token))
+                .await
+                .map_err(ErrorInternalServerError)?
+                .ok_or_else(|| ErrorUnauthorized("invalid or expired session token"))?;
+
+            Uuid::parse_str(&user_id)
+                .map(AuthenticatedUser)
+                .map_err(|_| ErrorUnauthorized("invalid session"))
+        })
+    }
+}
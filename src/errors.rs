@@ -0,0 +1,87 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+
+use crate::api_response::ApiResponse;
+
+/// Unified error type for everything that can go wrong while handling a
+/// request, so handlers can stop inventing their own response shapes.
+#[derive(Debug)]
+pub enum DomainError {
+    NotFound(String),
+    BadRequest(String),
+    DbError(String),
+    PoolError(String),
+    Validation(validator::ValidationErrors),
+}
+
+impl std::fmt::Display for DomainError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DomainError::NotFound(msg) => write!(f, "{msg}"),
+            DomainError::BadRequest(msg) => write!(f, "{msg}"),
+            DomainError::DbError(msg) => write!(f, "{msg}"),
+            DomainError::PoolError(msg) => write!(f, "{msg}"),
+            DomainError::Validation(errs) => write!(f, "{errs}"),
+        }
+    }
+}
+
+impl std::error::Error for DomainError {}
+
+impl ResponseError for DomainError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            DomainError::NotFound(_) => StatusCode::NOT_FOUND,
+            DomainError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            DomainError::DbError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            DomainError::PoolError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            DomainError::Validation(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            DomainError::Validation(errs) => HttpResponse::BadRequest()
+                .json(ApiResponse::<()>::validation_error(errs.clone())),
+            _ => HttpResponse::build(self.status_code()).json(ApiResponse::<()>::error(self.to_string())),
+        }
+    }
+}
+
+impl From<validator::ValidationErrors> for DomainError {
+    fn from(err: validator::ValidationErrors) -> Self {
+        DomainError::Validation(err)
+    }
+}
+
+impl From<diesel::result::Error> for DomainError {
+    fn from(err: diesel::result::Error) -> Self {
+        match err {
+            diesel::result::Error::NotFound => DomainError::NotFound(err.to_string()),
+            other => DomainError::DbError(other.to_string()),
+        }
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for DomainError {
+    fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        DomainError::DbError(err.to_string())
+    }
+}
+
+impl From<actix_web::error::BlockingError> for DomainError {
+    fn from(err: actix_web::error::BlockingError) -> Self {
+        DomainError::DbError(err.to_string())
+    }
+}
+
+impl From<deadpool_diesel::PoolError> for DomainError {
+    fn from(err: deadpool_diesel::PoolError) -> Self {
+        DomainError::PoolError(err.to_string())
+    }
+}
+
+impl From<deadpool_diesel::InteractError> for DomainError {
+    fn from(err: deadpool_diesel::InteractError) -> Self {
+        DomainError::DbError(err.to_string())
+    }
+}
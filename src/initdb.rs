@@ -0,0 +1,41 @@
+use deadpool_diesel::sqlite::{Manager, Pool};
+use deadpool_diesel::Runtime;
+use diesel::{Connection, SqliteConnection};
+
+use crate::errors::DomainError;
+use crate::migrator;
+
+/// Short-hand for the database pool type to use throughout the app.
+pub type DbPool = Pool;
+
+/// Initialize database connection pool based on `DATABASE_URL` environment variable.
+///
+/// Runs any pending embedded migrations first, so a fresh `DATABASE_URL`
+/// file is bootstrapped with the current schema automatically.
+///
+/// See more: <https://docs.rs/deadpool-diesel/latest/deadpool_diesel/>.
+pub fn initialize_db_pool() -> DbPool {
+    let conn_spec = std::env::var("DATABASE_URL").expect("DATABASE_URL should be set");
+
+    let mut conn = SqliteConnection::establish(&conn_spec)
+        .unwrap_or_else(|err| panic!("error connecting to {conn_spec}: {err}"));
+    migrator::run_migrations(&mut conn);
+
+    let manager = Manager::new(conn_spec, Runtime::Tokio1);
+    Pool::builder(manager)
+        .build()
+        .expect("database URL should be valid path to SQLite DB file")
+}
+
+/// Acquires a connection from `pool` asynchronously and runs `f` on a
+/// blocking thread, propagating a panic inside `f` as a `DomainError`.
+pub async fn run<F, T>(pool: &DbPool, f: F) -> Result<T, DomainError>
+where
+    F: FnOnce(&mut SqliteConnection) -> Result<T, Box<dyn std::error::Error + Send + Sync>>
+        + Send
+        + 'static,
+    T: Send + 'static,
+{
+    let conn = pool.get().await.map_err(DomainError::from)?;
+    conn.interact(f).await.map_err(DomainError::from)?.map_err(DomainError::from)
+}
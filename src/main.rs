@@ -1,8 +1,14 @@
 use actix_web::{middleware::Logger, web, App, HttpServer};
-use api::tasks::{add_task, delete_task, get_all_tasks, get_task};
+use api::auth::{login, register};
+use api::board::{create_board, delete_board, get_all_boards, get_board_summary, get_board_tasks};
+use api::tasks::{add_task, delete_task, get_all_tasks, get_task, update_task};
 
 mod api;
 mod actions;
+mod api_response;
+mod auth;
+mod errors;
+mod migrator;
 mod model;
 mod schema;
 mod initdb;
@@ -14,9 +20,17 @@ async fn main() -> std::io::Result<()> {
     std::env::set_var("RUST_BACKTRACE", "1");
     env_logger::init();
 
-    // initialize DB pool outside of `HttpServer::new` so that it is shared across all workers
+    // initialize DB pool outside of `HttpServer::new` so that it is shared across all workers;
+    // this also applies any pending migrations to `DATABASE_URL`
     let pool = initdb::initialize_db_pool();
 
+    // `--migrate` runs migrations (done above) and exits, for use in deploy scripts
+    // that want to bootstrap the schema without starting the HTTP server.
+    if std::env::args().any(|arg| arg == "--migrate") {
+        log::info!("ran pending migrations, exiting due to --migrate");
+        return Ok(());
+    }
+
     log::info!("starting HTTP server at http://localhost:8080");
 
     HttpServer::new(move || {
@@ -31,6 +45,14 @@ async fn main() -> std::io::Result<()> {
             .service(add_task)
             .service(get_all_tasks)
             .service(delete_task)
+            .service(update_task)
+            .service(create_board)
+            .service(get_all_boards)
+            .service(get_board_tasks)
+            .service(get_board_summary)
+            .service(delete_board)
+            .service(register)
+            .service(login)
     })
     .bind(("127.0.0.1", 8080))?
     .run()
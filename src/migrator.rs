@@ -0,0 +1,13 @@
+use diesel::SqliteConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+/// Migrations embedded into the binary at compile time, so a fresh
+/// `DATABASE_URL` file can be bootstrapped without a separate `diesel` CLI
+/// step.
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Applies any pending migrations to `conn`.
+pub fn run_migrations(conn: &mut SqliteConnection) {
+    conn.run_pending_migrations(MIGRATIONS)
+        .expect("failed to run database migrations");
+}
@@ -0,0 +1,17 @@
+use diesel::{Queryable, prelude::Insertable};
+use serde::{Deserialize, Serialize};
+use crate::schema::boards;
+
+/// board details.
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Insertable)]
+#[diesel(table_name = boards)]
+pub struct Board {
+    pub id: String,
+    pub name: String,
+}
+
+/// New board details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewBoard {
+    pub name: String,
+}
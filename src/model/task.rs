@@ -1,27 +1,89 @@
-use diesel::{Queryable, prelude::Insertable};
+use diesel::{AsChangeset, Queryable, prelude::Insertable};
 use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
 use crate::schema::tasks;
 
+/// The column a task currently sits in on its board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Queued,
+    InProgress,
+    Done,
+}
+
+impl TaskStatus {
+    /// The value stored in the `status` column.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Queued => "queued",
+            TaskStatus::InProgress => "in_progress",
+            TaskStatus::Done => "done",
+        }
+    }
+}
+
 /// task details.
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Insertable)]
 #[diesel(table_name = tasks)]
 pub struct Task {
     pub id: String,
     pub name: String,
-    pub done: bool
+    pub done: bool,
+    pub board_id: Option<String>,
+    pub status: String,
+    pub user_id: Option<String>,
 }
 
 /// New task details.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct NewTask {
+    #[validate(length(min = 1, max = 255))]
     pub name: String,
     pub done: bool,
+    pub board_id: Option<String>,
 }
 
 impl NewTask {
     /// Constructs new task details from name.
     #[cfg(test)] // only needed in tests
     pub fn new(name: impl Into<String>, done: impl Into<bool>) -> Self {
-        Self { name: name.into(), done: done.into() }
+        Self { name: name.into(), done: done.into(), board_id: None }
+    }
+}
+
+/// Partial task update. Only fields set to `Some` are applied.
+///
+/// `done` and `status` are kept in sync whenever only one of the two is
+/// given (see `actions::update_task`): setting just `done` derives
+/// `status`, and setting just `status` derives `done`. Set `status`
+/// explicitly (e.g. to `in_progress`) when `done` alone can't express the
+/// column a task should sit in; set both to override the derivation.
+#[derive(Debug, Clone, Serialize, Deserialize, AsChangeset, Validate)]
+#[diesel(table_name = tasks)]
+pub struct UpdateTask {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    pub done: Option<bool>,
+    #[validate(custom = "validate_status")]
+    pub status: Option<String>,
+}
+
+/// Validates that `status`, if present, is one of the canonical
+/// `TaskStatus::as_str()` values.
+fn validate_status(value: &str) -> Result<(), ValidationError> {
+    match value {
+        "queued" | "in_progress" | "done" => Ok(()),
+        _ => Err(ValidationError::new("invalid_status")),
     }
 }
+
+/// Query-string parameters accepted by `GET /tasks` for pagination and
+/// filtering.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaskListParams {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    pub done: Option<bool>,
+    pub name: Option<String>,
+}
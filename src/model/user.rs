@@ -0,0 +1,60 @@
+use diesel::{Queryable, prelude::Insertable};
+use serde::{Deserialize, Serialize};
+use crate::schema::{sessions, users};
+
+/// user details, as stored in the database.
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Insertable)]
+#[diesel(table_name = users)]
+pub struct User {
+    pub id: String,
+    pub username: String,
+    pub password_hash: String,
+}
+
+/// Registration payload. `password` is never stored as-is; see
+/// `api::auth::register`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewUser {
+    pub username: String,
+    pub password: String,
+}
+
+/// Login payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// `User` with the password hash stripped out, safe to return to clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserResponse {
+    pub id: String,
+    pub username: String,
+}
+
+impl From<User> for UserResponse {
+    fn from(user: User) -> Self {
+        Self { id: user.id, username: user.username }
+    }
+}
+
+/// An opaque, server-issued session token bound to a single user.
+///
+/// Clients authenticate by calling `POST /login` and sending the returned
+/// `token` back on the `Authorization: Bearer <token>` header on subsequent
+/// requests; see `auth::AuthenticatedUser`.
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Insertable)]
+#[diesel(table_name = sessions)]
+pub struct Session {
+    pub token: String,
+    pub user_id: String,
+}
+
+/// Response returned by `POST /login`: the session token plus the user it
+/// belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginResponse {
+    pub token: String,
+    pub user: UserResponse,
+}
@@ -1,25 +1,44 @@
 use diesel::prelude::*;
-use diesel::{SqliteConnection, r2d2};
 use actix_web::http::StatusCode;
 use actix_web::{middleware, web, App, test};
+use serde::Deserialize;
 use uuid::Uuid;
 
-// use crate::tests::initdb::initialize_db_pool;
-use crate::{get_task, add_task};
-use crate::model::task;
-
-/// Short-hand for the database pool type to use throughout the app.
-type DbPool = r2d2::Pool<r2d2::ConnectionManager<SqliteConnection>>;
-
-/// Initialize database connection pool based on `DATABASE_URL` environment variable.
-///
-/// See more: <https://docs.rs/diesel/latest/diesel/r2d2/index.html>.
-pub fn initialize_db_pool() -> DbPool {
-  let conn_spec = std::env::var("DATABASE_URL").expect("DATABASE_URL should be set");
-  let manager = r2d2::ConnectionManager::<SqliteConnection>::new(conn_spec);
-  r2d2::Pool::builder()
-      .build(manager)
-      .expect("database URL should be valid path to SQLite DB file")
+use std::collections::HashMap;
+
+use crate::initdb::initialize_db_pool;
+use crate::{get_task, add_task, update_task, get_all_tasks, delete_task, register, login};
+use crate::{create_board, delete_board, get_board_summary};
+use crate::model::{board, task, user};
+
+/// Minimal mirror of `ApiResponse<T>`'s success shape, just enough to pull
+/// `data` back out in tests without needing `ApiResponse` itself to derive
+/// `Deserialize`.
+#[derive(Deserialize)]
+struct Envelope<T> {
+    data: Option<T>,
+}
+
+/// Registers a fresh user (random username, fixed password) against `app`,
+/// logs in, and returns the `Authorization: Bearer <token>` header value.
+async fn login_as_new_user<S, B>(app: &S) -> String
+where
+    S: actix_web::dev::Service<actix_http::Request, Response = actix_web::dev::ServiceResponse<B>, Error = actix_web::Error>,
+    B: actix_web::body::MessageBody,
+{
+    let username = format!("user-{}", Uuid::new_v4());
+    let req = test::TestRequest::post()
+        .uri("/register")
+        .set_json(user::NewUser { username: username.clone(), password: "hunter2".to_string() })
+        .to_request();
+    test::call_service(app, req).await;
+
+    let req = test::TestRequest::post()
+        .uri("/login")
+        .set_json(user::Credentials { username, password: "hunter2".to_string() })
+        .to_request();
+    let login_res: user::LoginResponse = test::call_and_read_body_json(app, req).await;
+    format!("Bearer {}", login_res.token)
 }
 
 #[actix_web::test]
@@ -34,12 +53,20 @@ async fn task_routes() {
             .app_data(web::Data::new(pool.clone()))
             .wrap(middleware::Logger::default())
             .service(get_task)
-            .service(add_task),
+            .service(add_task)
+            .service(register)
+            .service(login),
     )
     .await;
 
+    // register and log in to get a real session token
+    let auth_header = login_as_new_user(&app).await;
+
     // send something that isn't a UUID to `get_task`
-    let req = test::TestRequest::get().uri("/task/123").to_request();
+    let req = test::TestRequest::get()
+        .uri("/task/123")
+        .insert_header(("Authorization", auth_header.clone()))
+        .to_request();
     let res = test::call_service(&app, req).await;
     assert_eq!(res.status(), StatusCode::NOT_FOUND);
     let body = test::read_body(res).await;
@@ -51,33 +78,362 @@ async fn task_routes() {
     // try to find a non-existent task
     let req = test::TestRequest::get()
         .uri(&format!("/task/{}", Uuid::nil()))
+        .insert_header(("Authorization", auth_header.clone()))
         .to_request();
     let res = test::call_service(&app, req).await;
     assert_eq!(res.status(), StatusCode::NOT_FOUND);
-    let body = test::read_body(res).await;
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["success"], false);
     assert!(
-        body.starts_with(b"No task found"),
+        body["error"].as_str().unwrap_or_default().starts_with("No task found"),
         "unexpected body: {body:?}",
     );
 
     // create new task
     let req = test::TestRequest::post()
         .uri("/task")
+        .insert_header(("Authorization", auth_header.clone()))
         .set_json(task::NewTask::new("Test task", false))
         .to_request();
-    let res: task::Task = test::call_and_read_body_json(&app, req).await;
+    let body: Envelope<task::Task> = test::call_and_read_body_json(&app, req).await;
+    let res = body.data.expect("created task should be present in the envelope");
     assert_eq!(res.name, "Test task");
 
     // get a task
     let req = test::TestRequest::get()
         .uri(&format!("/task/{}", res.id))
+        .insert_header(("Authorization", auth_header.clone()))
         .to_request();
-    let res: task::Task = test::call_and_read_body_json(&app, req).await;
+    let body: Envelope<task::Task> = test::call_and_read_body_json(&app, req).await;
+    let res = body.data.expect("fetched task should be present in the envelope");
     assert_eq!(res.name, "Test task");
 
     // delete new task from table
     use crate::schema::tasks::dsl::*;
-    diesel::delete(tasks.filter(id.eq(res.id)))
-        .execute(&mut pool.get().expect("couldn't get db connection from pool"))
+    let conn = pool.get().await.expect("couldn't get db connection from pool");
+    conn.interact(move |conn| {
+        diesel::delete(tasks.filter(id.eq(res.id))).execute(conn)
+    })
+    .await
+    .expect("interact task failed")
+    .expect("couldn't delete test task from table");
+}
+
+/// `PATCH /task/{id}` should apply partial updates and keep `status` in
+/// sync when only `done` is flipped.
+#[actix_web::test]
+async fn patch_task_updates_status() {
+    dotenv::dotenv().ok();
+
+    let pool = initialize_db_pool();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(add_task)
+            .service(update_task)
+            .service(register)
+            .service(login),
+    )
+    .await;
+
+    let auth_header = login_as_new_user(&app).await;
+
+    let req = test::TestRequest::post()
+        .uri("/task")
+        .insert_header(("Authorization", auth_header.clone()))
+        .set_json(task::NewTask::new("Write the docs", false))
+        .to_request();
+    let body: Envelope<task::Task> = test::call_and_read_body_json(&app, req).await;
+    let created = body.data.expect("created task should be present in the envelope");
+    assert_eq!(created.status, "queued");
+
+    // flipping `done` without mentioning `status` should still move it to "done"
+    let req = test::TestRequest::patch()
+        .uri(&format!("/task/{}", created.id))
+        .insert_header(("Authorization", auth_header.clone()))
+        .set_json(serde_json::json!({ "done": true }))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: Envelope<task::Task> = test::read_body_json(res).await;
+    let updated = body.data.expect("updated task should be present in the envelope");
+    assert!(updated.done);
+    assert_eq!(updated.status, "done");
+
+    use crate::schema::tasks::dsl::*;
+    let conn = pool.get().await.expect("couldn't get db connection from pool");
+    conn.interact(move |conn| diesel::delete(tasks.filter(id.eq(updated.id))).execute(conn))
+        .await
+        .expect("interact task failed")
+        .expect("couldn't delete test task from table");
+}
+
+/// `GET /board/{id}/summary` should report all three statuses, including
+/// ones with zero matching tasks.
+#[actix_web::test]
+async fn board_summary_reports_all_statuses() {
+    dotenv::dotenv().ok();
+
+    let pool = initialize_db_pool();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(create_board)
+            .service(delete_board)
+            .service(get_board_summary)
+            .service(add_task)
+            .service(register)
+            .service(login),
+    )
+    .await;
+
+    let auth_header = login_as_new_user(&app).await;
+
+    let req = test::TestRequest::post()
+        .uri("/board")
+        .set_json(board::NewBoard { name: "Sprint board".to_string() })
+        .to_request();
+    let body: Envelope<board::Board> = test::call_and_read_body_json(&app, req).await;
+    let created_board = body.data.expect("created board should be present in the envelope");
+
+    let req = test::TestRequest::post()
+        .uri("/task")
+        .insert_header(("Authorization", auth_header.clone()))
+        .set_json(task::NewTask { name: "Ship it".to_string(), done: false, board_id: Some(created_board.id.clone()) })
+        .to_request();
+    let body: Envelope<task::Task> = test::call_and_read_body_json(&app, req).await;
+    let created_task = body.data.expect("created task should be present in the envelope");
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/board/{}/summary", created_board.id))
+        .to_request();
+    let body: Envelope<HashMap<String, i64>> = test::call_and_read_body_json(&app, req).await;
+    let summary = body.data.expect("summary should be present in the envelope");
+    assert_eq!(summary.get("queued"), Some(&1));
+    assert_eq!(summary.get("in_progress"), Some(&0));
+    assert_eq!(summary.get("done"), Some(&0));
+
+    use crate::schema::tasks::dsl::*;
+    let conn = pool.get().await.expect("couldn't get db connection from pool");
+    conn.interact(move |conn| diesel::delete(tasks.filter(id.eq(created_task.id))).execute(conn))
+        .await
+        .expect("interact task failed")
         .expect("couldn't delete test task from table");
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/board/{}", created_board.id))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+/// `/register` then `/login` should return a usable session token, and
+/// task routes should reject requests with no token or an invalid token, and
+/// one user's token must not list another user's tasks (the IDOR this
+/// subsystem exists to prevent).
+#[actix_web::test]
+async fn auth_protects_task_routes() {
+    dotenv::dotenv().ok();
+
+    let pool = initialize_db_pool();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(add_task)
+            .service(get_all_tasks)
+            .service(register)
+            .service(login),
+    )
+    .await;
+
+    let alice_auth = login_as_new_user(&app).await;
+    let bob_auth = login_as_new_user(&app).await;
+
+    // unknown username
+    let req = test::TestRequest::post()
+        .uri("/login")
+        .set_json(user::Credentials { username: "nobody-at-all".to_string(), password: "wrong".to_string() })
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // no Authorization header at all
+    let req = test::TestRequest::get().uri("/tasks").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // garbage bearer token
+    let req = test::TestRequest::get()
+        .uri("/tasks")
+        .insert_header(("Authorization", "Bearer not-a-real-token"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    // alice creates a task; bob's (valid) token must not see it
+    let req = test::TestRequest::post()
+        .uri("/task")
+        .insert_header(("Authorization", alice_auth.clone()))
+        .set_json(task::NewTask::new("Alice's task", false))
+        .to_request();
+    let body: Envelope<task::Task> = test::call_and_read_body_json(&app, req).await;
+    let alice_task = body.data.expect("created task should be present in the envelope");
+
+    let req = test::TestRequest::get()
+        .uri("/tasks")
+        .insert_header(("Authorization", bob_auth))
+        .to_request();
+    let body: Envelope<crate::api_response::Page<task::Task>> =
+        test::call_and_read_body_json(&app, req).await;
+    let page = body.data.expect("page should be present in the envelope");
+    assert!(page.items.is_empty(), "bob should not see alice's tasks");
+
+    use crate::schema::tasks::dsl::*;
+    let conn = pool.get().await.expect("couldn't get db connection from pool");
+    conn.interact(move |conn| diesel::delete(tasks.filter(id.eq(alice_task.id))).execute(conn))
+        .await
+        .expect("interact task failed")
+        .expect("couldn't delete test task from table");
+}
+
+/// `GET /tasks` should paginate with `limit`/`offset`, report the total
+/// row count regardless of pagination, and filter by `done`/`name`.
+#[actix_web::test]
+async fn list_tasks_paginates_and_filters() {
+    dotenv::dotenv().ok();
+
+    let pool = initialize_db_pool();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(add_task)
+            .service(get_all_tasks)
+            .service(register)
+            .service(login),
+    )
+    .await;
+
+    let auth_header = login_as_new_user(&app).await;
+
+    let mut created_ids = Vec::new();
+    for (name, done) in [("Buy milk", false), ("Buy eggs", true), ("Walk the dog", false)] {
+        let req = test::TestRequest::post()
+            .uri("/task")
+            .insert_header(("Authorization", auth_header.clone()))
+            .set_json(task::NewTask::new(name, done))
+            .to_request();
+        let body: Envelope<task::Task> = test::call_and_read_body_json(&app, req).await;
+        created_ids.push(body.data.expect("created task should be present in the envelope").id);
+    }
+
+    // filter by done=true
+    let req = test::TestRequest::get()
+        .uri("/tasks?done=true")
+        .insert_header(("Authorization", auth_header.clone()))
+        .to_request();
+    let body: Envelope<crate::api_response::Page<task::Task>> =
+        test::call_and_read_body_json(&app, req).await;
+    let page = body.data.expect("page should be present in the envelope");
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].name, "Buy eggs");
+
+    // filter by name substring
+    let req = test::TestRequest::get()
+        .uri("/tasks?name=Buy")
+        .insert_header(("Authorization", auth_header.clone()))
+        .to_request();
+    let body: Envelope<crate::api_response::Page<task::Task>> =
+        test::call_and_read_body_json(&app, req).await;
+    let page = body.data.expect("page should be present in the envelope");
+    assert_eq!(page.total, 2);
+
+    // paginate: limit=1 offset=1, total should still reflect all 3 tasks
+    let req = test::TestRequest::get()
+        .uri("/tasks?limit=1&offset=1")
+        .insert_header(("Authorization", auth_header.clone()))
+        .to_request();
+    let body: Envelope<crate::api_response::Page<task::Task>> =
+        test::call_and_read_body_json(&app, req).await;
+    let page = body.data.expect("page should be present in the envelope");
+    assert_eq!(page.total, 3);
+    assert_eq!(page.items.len(), 1);
+
+    use crate::schema::tasks::dsl::*;
+    let conn = pool.get().await.expect("couldn't get db connection from pool");
+    conn.interact(move |conn| {
+        diesel::delete(tasks.filter(id.eq_any(created_ids))).execute(conn)
+    })
+    .await
+    .expect("interact task failed")
+    .expect("couldn't delete test tasks from table");
+}
+
+/// `POST /task` with an invalid payload (empty `name`) should be rejected
+/// with `400` and field-level validation errors, not inserted.
+#[actix_web::test]
+async fn add_task_rejects_invalid_payload() {
+    dotenv::dotenv().ok();
+
+    let pool = initialize_db_pool();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(add_task)
+            .service(register)
+            .service(login),
+    )
+    .await;
+
+    let auth_header = login_as_new_user(&app).await;
+
+    let req = test::TestRequest::post()
+        .uri("/task")
+        .insert_header(("Authorization", auth_header))
+        .set_json(task::NewTask::new("", false))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["success"], false);
+    assert!(body["errors"]["name"].is_array(), "unexpected body: {body:?}");
+}
+
+/// `DomainError` variants should surface through `ApiResponse`'s envelope
+/// with the right HTTP status, not just a bare 200/500.
+#[actix_web::test]
+async fn domain_error_envelopes_have_correct_status() {
+    dotenv::dotenv().ok();
+
+    let pool = initialize_db_pool();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(delete_task)
+            .service(register)
+            .service(login),
+    )
+    .await;
+
+    let auth_header = login_as_new_user(&app).await;
+
+    // DomainError::NotFound should come back as 404 with the shared envelope
+    let req = test::TestRequest::delete()
+        .uri(&format!("/tasks/{}", Uuid::nil()))
+        .insert_header(("Authorization", auth_header))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["success"], false);
+    assert!(body["data"].is_null());
+    assert!(body["error"].as_str().unwrap_or_default().contains("No task found"));
 }